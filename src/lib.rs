@@ -1,15 +1,118 @@
+use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint,
     entrypoint::ProgramResult,
     msg,
+    program::{invoke, invoke_signed},
     program_error::ProgramError,
     pubkey::Pubkey,
+    rent::Rent,
+    system_instruction,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+    sysvar::Sysvar,
 };
 
 // Entry point
 entrypoint!(process_instruction);
 
+/// Current on-chain layout version for `Counter`.
+pub const COUNTER_VERSION: u8 = 1;
+
+/// On-chain state for a counter account, serialized with Borsh so the
+/// layout is self-describing and can evolve without breaking clients.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Counter {
+    pub version: u8,
+    pub is_initialized: bool,
+    pub count: u64,
+}
+
+/// Encoded size in bytes of `Counter` (`u8` + `bool` + `u64`).
+pub const COUNTER_LEN: usize = 10;
+
+/// Errors specific to counter arithmetic, surfaced to clients as
+/// `ProgramError::Custom` codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CounterError {
+    /// Checked increment would overflow `u64::MAX`.
+    Overflow,
+    /// Checked decrement would underflow below `0`.
+    Underflow,
+}
+
+impl From<CounterError> for ProgramError {
+    fn from(e: CounterError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+/// Overflow/underflow behavior for increment and decrement instructions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArithmeticMode {
+    Saturating,
+    Wrapping,
+    Checked,
+}
+
+impl ArithmeticMode {
+    fn from_byte(byte: u8) -> Result<Self, ProgramError> {
+        match byte {
+            0 => Ok(ArithmeticMode::Saturating),
+            1 => Ok(ArithmeticMode::Wrapping),
+            2 => Ok(ArithmeticMode::Checked),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+
+    fn apply_increment(self, count: u64) -> Result<u64, ProgramError> {
+        match self {
+            ArithmeticMode::Saturating => Ok(count.saturating_add(1)),
+            ArithmeticMode::Wrapping => Ok(count.wrapping_add(1)),
+            ArithmeticMode::Checked => count
+                .checked_add(1)
+                .ok_or_else(|| CounterError::Overflow.into()),
+        }
+    }
+
+    fn apply_decrement(self, count: u64) -> Result<u64, ProgramError> {
+        match self {
+            ArithmeticMode::Saturating => Ok(count.saturating_sub(1)),
+            ArithmeticMode::Wrapping => Ok(count.wrapping_sub(1)),
+            ArithmeticMode::Checked => count
+                .checked_sub(1)
+                .ok_or_else(|| CounterError::Underflow.into()),
+        }
+    }
+}
+
+/// Seed prefix for per-owner counter PDAs.
+pub const COUNTER_SEED_PREFIX: &[u8] = b"counter";
+
+/// Derive the deterministic counter address for a given owner, i.e. the
+/// PDA at seeds `[b"counter", owner]` under `program_id`.
+pub fn find_counter_address(owner: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[COUNTER_SEED_PREFIX, owner.as_ref()], program_id)
+}
+
+impl Counter {
+    /// Deserialize a `Counter` from the account's data slice. Uses
+    /// `deserialize` rather than `try_from_slice` so accounts with slack
+    /// space reserved for future fields remain readable.
+    pub fn load(account: &AccountInfo) -> Result<Counter, ProgramError> {
+        let data = account.try_borrow_data()?;
+        Counter::deserialize(&mut &data[..]).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Serialize this `Counter` into the account's data slice.
+    pub fn store(&self, account: &AccountInfo) -> ProgramResult {
+        let mut data = account.try_borrow_mut_data()?;
+        self.serialize(&mut &mut data[..])
+            .map_err(|_| ProgramError::AccountDataTooSmall)?;
+        Ok(())
+    }
+}
+
 pub fn process_instruction(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -31,7 +134,61 @@ pub fn process_instruction(
             let instruction_data = &instruction_data[1..];
             initialize_account(program_id, accounts, instruction_data)
         }
-        1 => increment_counter(accounts),
+        1 => {
+            if instruction_data.len() != 2 {
+                msg!("Invalid instruction data length");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+            increment_counter(accounts, instruction_data[1])
+        }
+        2 => {
+            if instruction_data.len() != 17 {
+                msg!("Invalid instruction data length");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+            let instruction_data = &instruction_data[1..];
+            create_and_initialize_account(program_id, accounts, instruction_data)
+        }
+        3 => {
+            if instruction_data.len() != 18 {
+                msg!("Invalid instruction data length");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+            let instruction_data = &instruction_data[1..];
+            create_pda_counter(program_id, accounts, instruction_data)
+        }
+        4 => {
+            if instruction_data.len() != 3 {
+                msg!("Invalid instruction data length");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+            let instruction_data = &instruction_data[1..];
+            increment_pda_counter(program_id, accounts, instruction_data)
+        }
+        5 => {
+            if instruction_data.len() < 3 {
+                msg!("Invalid instruction data length");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+            let instruction_data = &instruction_data[1..];
+            increment_counter_guarded(program_id, accounts, instruction_data)
+        }
+        6 => {
+            if instruction_data.len() != 2 {
+                msg!("Invalid instruction data length");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+            decrement_counter(accounts, instruction_data[1])
+        }
+        7 => {
+            if instruction_data.len() != 9 {
+                msg!("Invalid instruction data length");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+            let instruction_data = &instruction_data[1..];
+            set_counter(accounts, instruction_data)
+        }
+        8 => reset_counter(accounts),
         _ => {
             msg!("Invalid instruction type");
             Err(ProgramError::InvalidInstructionData)
@@ -63,12 +220,20 @@ pub fn initialize_account(
     }
 
     // Check if the account has already been initialized
-    let mut data = account.try_borrow_mut_data()?;
-    if data.iter().any(|&x| x != 0) {
+    let counter = Counter::load(account)?;
+    if counter.is_initialized {
         msg!("Account already initialized");
         return Err(ProgramError::AccountAlreadyInitialized);
     }
 
+    // Guard against accounts that can be garbage-collected: a counter must
+    // hold enough lamports to be rent-exempt before we initialize it.
+    let rent = Rent::get()?;
+    if !rent.is_exempt(account.lamports(), account.data_len()) {
+        msg!("Account is not rent-exempt");
+        return Err(ProgramError::AccountNotRentExempt);
+    }
+
     // Initialize the account with the provided value
     let initial_value = u64::from_le_bytes(
         instruction_data
@@ -76,7 +241,12 @@ pub fn initialize_account(
             .map_err(|_| ProgramError::InvalidInstructionData)?,
     );
 
-    data[..8].copy_from_slice(&initial_value.to_le_bytes());
+    let counter = Counter {
+        version: COUNTER_VERSION,
+        is_initialized: true,
+        count: initial_value,
+    };
+    counter.store(account)?;
 
     msg!(
         "Account initialized successfully with value: {}",
@@ -85,8 +255,10 @@ pub fn initialize_account(
     Ok(())
 }
 
-// Instruction 1: Increment the counter
-pub fn increment_counter(accounts: &[AccountInfo]) -> ProgramResult {
+// Instruction 1: Increment the counter, with a mode byte selecting
+// saturating, wrapping, or checked overflow semantics.
+pub fn increment_counter(accounts: &[AccountInfo], mode: u8) -> ProgramResult {
+    let mode = ArithmeticMode::from_byte(mode)?;
     let accounts_iter = &mut accounts.iter();
 
     // Get the account to increment
@@ -99,17 +271,368 @@ pub fn increment_counter(accounts: &[AccountInfo]) -> ProgramResult {
     }
 
     // Increment the counter stored in the account's data
-    let mut data = account.try_borrow_mut_data()?;
-    if data.len() < 8 {
-        msg!("Account data is too small");
-        return Err(ProgramError::InvalidAccountData);
+    let mut counter = Counter::load(account)?;
+    if !counter.is_initialized {
+        msg!("Account is not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    counter.count = mode.apply_increment(counter.count)?;
+    counter.store(account)?;
+    msg!("Counter incremented to: {}", counter.count);
+
+    Ok(())
+}
+
+// Instruction 6: Decrement the counter, with a mode byte selecting
+// saturating, wrapping, or checked underflow semantics.
+pub fn decrement_counter(accounts: &[AccountInfo], mode: u8) -> ProgramResult {
+    let mode = ArithmeticMode::from_byte(mode)?;
+    let accounts_iter = &mut accounts.iter();
+
+    let account = next_account_info(accounts_iter)?;
+
+    if !account.is_writable {
+        msg!("Account is not writable");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut counter = Counter::load(account)?;
+    if !counter.is_initialized {
+        msg!("Account is not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    counter.count = mode.apply_decrement(counter.count)?;
+    counter.store(account)?;
+    msg!("Counter decremented to: {}", counter.count);
+
+    Ok(())
+}
+
+// Instruction 7: Set the counter to a client-supplied value.
+pub fn set_counter(accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let account = next_account_info(accounts_iter)?;
+
+    if !account.is_writable {
+        msg!("Account is not writable");
+        return Err(ProgramError::InvalidArgument);
     }
 
-    let counter = u64::from_le_bytes(data[..8].try_into().unwrap());
-    let new_counter = counter.wrapping_add(1);
+    let mut counter = Counter::load(account)?;
+    if !counter.is_initialized {
+        msg!("Account is not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let value = u64::from_le_bytes(
+        instruction_data
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?,
+    );
 
-    data[..8].copy_from_slice(&new_counter.to_le_bytes());
-    msg!("Counter incremented to: {}", new_counter);
+    counter.count = value;
+    counter.store(account)?;
+    msg!("Counter set to: {}", counter.count);
+
+    Ok(())
+}
+
+// Instruction 8: Reset the counter to zero.
+pub fn reset_counter(accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let account = next_account_info(accounts_iter)?;
+
+    if !account.is_writable {
+        msg!("Account is not writable");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut counter = Counter::load(account)?;
+    if !counter.is_initialized {
+        msg!("Account is not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    counter.count = 0;
+    counter.store(account)?;
+    msg!("Counter reset to 0");
+
+    Ok(())
+}
+
+// Instruction 2: Create the counter account via a System Program CPI, then
+// initialize it. This lets a client fund and initialize a counter in a
+// single transaction instead of pre-creating the account out of band.
+pub fn create_and_initialize_account(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    // Funds the new account and signs for its creation.
+    let payer = next_account_info(accounts_iter)?;
+    // The counter account being created; must also sign, per the System
+    // Program's `create_account` requirements.
+    let new_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    if !payer.is_signer || !new_account.is_signer {
+        msg!("Payer and new account must sign the transaction");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let initial_value = u64::from_le_bytes(
+        instruction_data[..8]
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?,
+    );
+    let space = u64::from_le_bytes(
+        instruction_data[8..16]
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?,
+    );
+
+    if space as usize != COUNTER_LEN {
+        msg!("Requested space does not match the Counter encoding");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let rent = Rent::get()?;
+    let lamports = rent.minimum_balance(space as usize);
+
+    invoke(
+        &system_instruction::create_account(
+            payer.key,
+            new_account.key,
+            lamports,
+            space,
+            program_id,
+        ),
+        &[payer.clone(), new_account.clone(), system_program.clone()],
+    )?;
+
+    let counter = Counter {
+        version: COUNTER_VERSION,
+        is_initialized: true,
+        count: initial_value,
+    };
+    counter.store(new_account)?;
+
+    msg!(
+        "Account created and initialized successfully with value: {}",
+        initial_value
+    );
+    Ok(())
+}
+
+// Instruction 3: Create a per-owner counter at its PDA, signing for the
+// System Program CPI with the derived seeds instead of a keypair.
+pub fn create_pda_counter(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    // Owner whose PDA is being created; also pays for the account.
+    let payer = next_account_info(accounts_iter)?;
+    let pda_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    if !payer.is_signer {
+        msg!("Owner must sign the transaction");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let initial_value = u64::from_le_bytes(
+        instruction_data[..8]
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?,
+    );
+    let space = u64::from_le_bytes(
+        instruction_data[8..16]
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?,
+    );
+    let bump = instruction_data[16];
+
+    if space as usize != COUNTER_LEN {
+        msg!("Requested space does not match the Counter encoding");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let (expected_pda, expected_bump) = find_counter_address(payer.key, program_id);
+    if expected_pda != *pda_account.key || expected_bump != bump {
+        msg!("Counter account does not match the derived PDA");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let rent = Rent::get()?;
+    let lamports = rent.minimum_balance(space as usize);
+    let seeds: &[&[u8]] = &[COUNTER_SEED_PREFIX, payer.key.as_ref(), &[bump]];
+
+    invoke_signed(
+        &system_instruction::create_account(
+            payer.key,
+            pda_account.key,
+            lamports,
+            space,
+            program_id,
+        ),
+        &[payer.clone(), pda_account.clone(), system_program.clone()],
+        &[seeds],
+    )?;
+
+    let counter = Counter {
+        version: COUNTER_VERSION,
+        is_initialized: true,
+        count: initial_value,
+    };
+    counter.store(pda_account)?;
+
+    msg!(
+        "PDA counter created and initialized successfully with value: {}",
+        initial_value
+    );
+    Ok(())
+}
+
+// Instruction 4: Increment a per-owner PDA counter, rejecting accounts that
+// do not match the re-derived PDA for the given owner.
+pub fn increment_pda_counter(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let owner = next_account_info(accounts_iter)?;
+    let pda_account = next_account_info(accounts_iter)?;
+
+    let bump = instruction_data[0];
+    let mode = ArithmeticMode::from_byte(instruction_data[1])?;
+    let (expected_pda, expected_bump) = find_counter_address(owner.key, program_id);
+    if expected_pda != *pda_account.key || expected_bump != bump {
+        msg!("Counter account does not match the derived PDA");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if !pda_account.is_writable {
+        msg!("Account is not writable");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut counter = Counter::load(pda_account)?;
+    if !counter.is_initialized {
+        msg!("Account is not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    counter.count = mode.apply_increment(counter.count)?;
+    counter.store(pda_account)?;
+    msg!("PDA counter incremented to: {}", counter.count);
+
+    Ok(())
+}
+
+/// Guard mode byte for [`increment_counter_guarded`].
+///
+/// `0`: reject the transaction if any sibling instruction also targets this
+/// program with an increment opcode (`1`, `4`, or `5`).
+/// `1`: require a sibling instruction whose program id matches the 32-byte
+/// companion program id that follows the guard mode and arithmetic mode
+/// bytes.
+pub const INCREMENT_GUARD_MODE_REJECT_DUPLICATE: u8 = 0;
+pub const INCREMENT_GUARD_MODE_REQUIRE_COMPANION: u8 = 1;
+
+fn is_increment_opcode(data: &[u8]) -> bool {
+    matches!(data.first(), Some(1) | Some(4) | Some(5))
+}
+
+// Instruction 5: Increment the counter, but only after inspecting sibling
+// instructions in the same transaction via the Instructions sysvar. Guards
+// against accidental double-counting or enforces that a companion
+// instruction (e.g. a memo or transfer) accompanies the increment.
+pub fn increment_counter_guarded(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let account = next_account_info(accounts_iter)?;
+    let instructions_sysvar = next_account_info(accounts_iter)?;
+
+    if !account.is_writable {
+        msg!("Account is not writable");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mode = instruction_data[0];
+    let arithmetic_mode = ArithmeticMode::from_byte(instruction_data[1])?;
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+
+    match mode {
+        INCREMENT_GUARD_MODE_REJECT_DUPLICATE => {
+            let mut index: u16 = 0;
+            while let Ok(instruction) =
+                load_instruction_at_checked(index as usize, instructions_sysvar)
+            {
+                if index != current_index
+                    && instruction.program_id == *program_id
+                    && is_increment_opcode(&instruction.data)
+                {
+                    msg!("More than one increment instruction targets this program");
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                index += 1;
+            }
+        }
+        INCREMENT_GUARD_MODE_REQUIRE_COMPANION => {
+            let companion_program_id = Pubkey::new_from_array(
+                instruction_data
+                    .get(2..34)
+                    .ok_or(ProgramError::InvalidInstructionData)?
+                    .try_into()
+                    .map_err(|_| ProgramError::InvalidInstructionData)?,
+            );
+
+            let mut found_companion = false;
+            let mut index: u16 = 0;
+            while let Ok(instruction) =
+                load_instruction_at_checked(index as usize, instructions_sysvar)
+            {
+                if index != current_index && instruction.program_id == companion_program_id {
+                    found_companion = true;
+                    break;
+                }
+                index += 1;
+            }
+            if !found_companion {
+                msg!("Required companion instruction is missing from the transaction");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+        }
+        _ => {
+            msg!("Invalid increment guard mode");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+    }
+
+    let mut counter = Counter::load(account)?;
+    if !counter.is_initialized {
+        msg!("Account is not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    counter.count = arithmetic_mode.apply_increment(counter.count)?;
+    counter.store(account)?;
+    msg!("Counter incremented to: {} (guarded)", counter.count);
 
     Ok(())
 }
@@ -118,18 +641,94 @@ pub fn increment_counter(accounts: &[AccountInfo]) -> ProgramResult {
 mod tests {
     use super::*;
 
-    use solana_program::clock::Epoch; // Import Pack trait for Rent serialization
+    use solana_program::clock::Epoch;
+    use solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+    use solana_program::sysvar::instructions::{
+        construct_instructions_data, store_current_index, BorrowedInstruction,
+    };
+
+    // Stands in for the runtime's `sol_get_rent_sysvar` syscall: `Rent::get()`
+    // has no account to read from in a unit test, so tests that need it install
+    // this stub; it stays installed for the rest of the test process.
+    struct RentSyscallStub {
+        rent: Rent,
+    }
+
+    impl SyscallStubs for RentSyscallStub {
+        fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+            unsafe {
+                *(var_addr as *mut Rent) = self.rent.clone();
+            }
+            solana_program::entrypoint::SUCCESS
+        }
+    }
+
+    fn install_rent(rent: Rent) {
+        set_syscall_stubs(Box::new(RentSyscallStub { rent }));
+    }
+
+    // Builds an Instructions sysvar `AccountInfo` whose data mirrors what the
+    // runtime would serialize for the given sibling instructions, with
+    // `current_index` marking the instruction under test.
+    fn instructions_sysvar_account<'a>(
+        lamports: &'a mut u64,
+        data: &'a mut Vec<u8>,
+        instructions: &[BorrowedInstruction],
+        current_index: u16,
+    ) -> AccountInfo<'a> {
+        *data = construct_instructions_data(instructions);
+        store_current_index(data, current_index);
+        AccountInfo::new(
+            &solana_program::sysvar::instructions::ID,
+            false,
+            false,
+            lamports,
+            data,
+            &solana_program::sysvar::instructions::ID,
+            false,
+            Epoch::default(),
+        )
+    }
 
     #[test]
     fn test_initialize_account() {
         let program_id = Pubkey::new_unique();
         let key = Pubkey::new_unique();
-        let payer_key = Pubkey::new_unique();
 
-        let mut lamports = 1_000_000; // Arbitrary amount for tests
-        let mut payer_lamports = lamports;
+        let mut lamports = 1_000_000; // Enough to be rent-exempt for this data size
+        let mut data = vec![0; 10];
+        let account = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            Epoch::default(),
+        );
+
+        install_rent(Rent::default());
+
+        let accounts = vec![account.clone()];
+        let instruction_data = [0, 1, 0, 0, 0, 0, 0, 0, 0]; // Initialize with value 1
+
+        let result = process_instruction(&program_id, &accounts, &instruction_data);
+        assert_eq!(result.is_ok(), true, "{:?}", result);
+
+        // Verify account data
+        let counter = Counter::load(&account).unwrap();
+        assert_eq!(counter.is_initialized, true);
+        assert_eq!(counter.count, 1);
+    }
+
+    #[test]
+    fn test_initialize_account_not_rent_exempt() {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
 
-        let mut data = vec![0; 8];
+        let mut lamports = 100; // Far below the rent-exempt minimum
+        let mut data = vec![0; 10];
         let account = AccountInfo::new(
             &key,
             false,
@@ -141,40 +740,170 @@ mod tests {
             Epoch::default(),
         );
 
-        let mut payer_data = vec![0; 0];
-        let payer_account = AccountInfo::new(
-            &payer_key,
+        install_rent(Rent::default());
+
+        let accounts = vec![account.clone()];
+        let instruction_data = [0, 1, 0, 0, 0, 0, 0, 0, 0]; // Initialize with value 1
+
+        let result = process_instruction(&program_id, &accounts, &instruction_data);
+        assert_eq!(
+            result,
+            Err(ProgramError::AccountNotRentExempt),
+            "{:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_increment_counter() {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut lamports = 1_000_000;
+        let mut data = Counter {
+            version: COUNTER_VERSION,
+            is_initialized: true,
+            count: 1,
+        }
+        .try_to_vec()
+        .unwrap();
+
+        let account = AccountInfo::new(
+            &key,
+            false,
             true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            Epoch::default(),
+        );
+
+        let accounts = vec![account.clone()];
+        let instruction_data = [1, 1]; // Increment instruction, wrapping mode
+
+        let result = process_instruction(&program_id, &accounts, &instruction_data);
+        assert!(result.is_ok(), "Failed to increment counter: {:?}", result);
+        let counter = Counter::load(&account).unwrap();
+        assert_eq!(counter.count, 2, "Counter was not incremented correctly");
+    }
+
+    #[test]
+    fn test_increment_counter_checked_overflow() {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut lamports = 1_000_000;
+        let mut data = Counter {
+            version: COUNTER_VERSION,
+            is_initialized: true,
+            count: u64::MAX,
+        }
+        .try_to_vec()
+        .unwrap();
+
+        let account = AccountInfo::new(
+            &key,
+            false,
             true,
-            &mut payer_lamports,
-            &mut payer_data,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            Epoch::default(),
+        );
+
+        let accounts = vec![account.clone()];
+        let instruction_data = [1, 2]; // Increment instruction, checked mode
+
+        let result = process_instruction(&program_id, &accounts, &instruction_data);
+        assert_eq!(
+            result,
+            Err(CounterError::Overflow.into()),
+            "Checked increment should fail on overflow: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_decrement_counter() {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut lamports = 1_000_000;
+        let mut data = Counter {
+            version: COUNTER_VERSION,
+            is_initialized: true,
+            count: 1,
+        }
+        .try_to_vec()
+        .unwrap();
+
+        let account = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
             &program_id,
             false,
             Epoch::default(),
         );
 
-        let accounts = vec![account.clone(), payer_account.clone()];
-        let instruction_data = [1, 0, 0, 0, 0, 0, 0, 0, 0]; // Initialize with value 1
+        let accounts = vec![account.clone()];
+        let instruction_data = [6, 1]; // Decrement instruction, wrapping mode
 
         let result = process_instruction(&program_id, &accounts, &instruction_data);
-        assert_eq!(result.is_ok(), true, "{:?}", result);
+        assert!(result.is_ok(), "Failed to decrement counter: {:?}", result);
+        let counter = Counter::load(&account).unwrap();
+        assert_eq!(counter.count, 0, "Counter was not decremented correctly");
+    }
 
-        // Verify account data
-        let account_data = account.try_borrow_data().unwrap();
+    #[test]
+    fn test_decrement_counter_checked_underflow() {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut lamports = 1_000_000;
+        let mut data = Counter {
+            version: COUNTER_VERSION,
+            is_initialized: true,
+            count: 0,
+        }
+        .try_to_vec()
+        .unwrap();
+
+        let account = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            Epoch::default(),
+        );
+
+        let accounts = vec![account.clone()];
+        let instruction_data = [6, 2]; // Decrement instruction, checked mode
+
+        let result = process_instruction(&program_id, &accounts, &instruction_data);
         assert_eq!(
-            account_data[..8],
-            1u64.to_le_bytes(),
-            "data not matching {:?}",
-            account_data
+            result,
+            Err(CounterError::Underflow.into()),
+            "Checked decrement should fail on underflow: {:?}",
+            result
         );
     }
 
     #[test]
-    fn test_increment_counter() {
+    fn test_set_counter() {
         let program_id = Pubkey::new_unique();
         let key = Pubkey::new_unique();
         let mut lamports = 1_000_000;
-        let mut data = 1u64.to_le_bytes().to_vec();
+        let mut data = Counter {
+            version: COUNTER_VERSION,
+            is_initialized: true,
+            count: 1,
+        }
+        .try_to_vec()
+        .unwrap();
 
         let account = AccountInfo::new(
             &key,
@@ -188,14 +917,311 @@ mod tests {
         );
 
         let accounts = vec![account.clone()];
-        let instruction_data = [1]; // Increment instruction
+        let instruction_data = [7, 42, 0, 0, 0, 0, 0, 0, 0]; // Set instruction, value 42
 
         let result = process_instruction(&program_id, &accounts, &instruction_data);
-        assert!(result.is_ok(), "Failed to increment counter: {:?}", result);
+        assert!(result.is_ok(), "Failed to set counter: {:?}", result);
+        let counter = Counter::load(&account).unwrap();
+        assert_eq!(counter.count, 42, "Counter was not set correctly");
+    }
+
+    #[test]
+    fn test_reset_counter() {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut lamports = 1_000_000;
+        let mut data = Counter {
+            version: COUNTER_VERSION,
+            is_initialized: true,
+            count: 42,
+        }
+        .try_to_vec()
+        .unwrap();
+
+        let account = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            Epoch::default(),
+        );
+
+        let accounts = vec![account.clone()];
+        let instruction_data = [8]; // Reset instruction
+
+        let result = process_instruction(&program_id, &accounts, &instruction_data);
+        assert!(result.is_ok(), "Failed to reset counter: {:?}", result);
+        let counter = Counter::load(&account).unwrap();
+        assert_eq!(counter.count, 0, "Counter was not reset correctly");
+    }
+
+    fn system_program_id() -> Pubkey {
+        Pubkey::new_from_array([0; 32])
+    }
+
+    #[test]
+    fn test_find_counter_address_is_deterministic() {
+        let program_id = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+
+        let (pda, bump) = find_counter_address(&owner, &program_id);
+        let (pda_again, bump_again) = find_counter_address(&owner, &program_id);
+        assert_eq!(pda, pda_again);
+        assert_eq!(bump, bump_again);
+
+        let (other_pda, _) = find_counter_address(&Pubkey::new_unique(), &program_id);
+        assert_ne!(pda, other_pda, "distinct owners must derive distinct PDAs");
+    }
+
+    #[test]
+    fn test_create_pda_counter_rejects_mismatched_pda() {
+        let program_id = Pubkey::new_unique();
+        let payer_key = Pubkey::new_unique();
+        let wrong_pda_key = Pubkey::new_unique(); // not the derived PDA for payer_key
+
+        let mut payer_lamports = 1_000_000;
+        let mut payer_data = Vec::new();
+        let payer = AccountInfo::new(
+            &payer_key,
+            true,
+            false,
+            &mut payer_lamports,
+            &mut payer_data,
+            &system_program_id(),
+            false,
+            Epoch::default(),
+        );
+
+        let mut pda_lamports = 0;
+        let mut pda_data = Vec::new();
+        let pda_account = AccountInfo::new(
+            &wrong_pda_key,
+            false,
+            true,
+            &mut pda_lamports,
+            &mut pda_data,
+            &system_program_id(),
+            false,
+            Epoch::default(),
+        );
+
+        let mut system_lamports = 0;
+        let mut system_data = Vec::new();
+        let system_program = AccountInfo::new(
+            &system_program_id(),
+            false,
+            false,
+            &mut system_lamports,
+            &mut system_data,
+            &system_program_id(),
+            false,
+            Epoch::default(),
+        );
+
+        let (_, real_bump) = find_counter_address(&payer_key, &program_id);
+        let mut instruction_data = vec![3u8];
+        instruction_data.extend_from_slice(&1u64.to_le_bytes()); // initial value
+        instruction_data.extend_from_slice(&(COUNTER_LEN as u64).to_le_bytes()); // space
+        instruction_data.push(real_bump);
+
+        let accounts = vec![payer.clone(), pda_account.clone(), system_program.clone()];
+        let result = process_instruction(&program_id, &accounts, &instruction_data);
+        assert_eq!(result, Err(ProgramError::InvalidSeeds), "{:?}", result);
+    }
+
+    #[test]
+    fn test_increment_pda_counter_rejects_mismatched_pda() {
+        let program_id = Pubkey::new_unique();
+        let owner_key = Pubkey::new_unique();
+        let wrong_pda_key = Pubkey::new_unique(); // not the derived PDA for owner_key
+
+        let mut owner_lamports = 0;
+        let mut owner_data = Vec::new();
+        let owner = AccountInfo::new(
+            &owner_key,
+            true,
+            false,
+            &mut owner_lamports,
+            &mut owner_data,
+            &system_program_id(),
+            false,
+            Epoch::default(),
+        );
+
+        let mut lamports = 1_000_000;
+        let mut data = Counter {
+            version: COUNTER_VERSION,
+            is_initialized: true,
+            count: 1,
+        }
+        .try_to_vec()
+        .unwrap();
+        let pda_account = AccountInfo::new(
+            &wrong_pda_key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            Epoch::default(),
+        );
+
+        let (_, real_bump) = find_counter_address(&owner_key, &program_id);
+        let accounts = vec![owner.clone(), pda_account.clone()];
+        let instruction_data = [4, real_bump, 0]; // saturating mode
+
+        let result = process_instruction(&program_id, &accounts, &instruction_data);
+        assert_eq!(result, Err(ProgramError::InvalidSeeds), "{:?}", result);
+    }
+
+    // Builds a guarded-increment account set (counter + instructions sysvar)
+    // for the given guard-mode payload, with `siblings` standing in for the
+    // other instructions in the transaction.
+    fn run_increment_counter_guarded(
+        program_id: &Pubkey,
+        mode_and_payload: &[u8],
+        siblings: Vec<BorrowedInstruction>,
+        current_index: u16,
+    ) -> ProgramResult {
+        let key = Pubkey::new_unique();
+        let mut lamports = 1_000_000;
+        let mut data = Counter {
+            version: COUNTER_VERSION,
+            is_initialized: true,
+            count: 1,
+        }
+        .try_to_vec()
+        .unwrap();
+        let account = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            program_id,
+            false,
+            Epoch::default(),
+        );
+
+        let mut sysvar_lamports = 0;
+        let mut sysvar_data = Vec::new();
+        let instructions_sysvar = instructions_sysvar_account(
+            &mut sysvar_lamports,
+            &mut sysvar_data,
+            &siblings,
+            current_index,
+        );
+
+        let accounts = vec![account.clone(), instructions_sysvar.clone()];
+        let mut instruction_data = vec![5u8];
+        instruction_data.extend_from_slice(mode_and_payload);
+
+        process_instruction(program_id, &accounts, &instruction_data)
+    }
+
+    #[test]
+    fn test_increment_counter_guarded_reject_duplicate_allows_lone_increment() {
+        let program_id = Pubkey::new_unique();
+        let this_instruction = BorrowedInstruction {
+            program_id: &program_id,
+            accounts: vec![],
+            data: &[1, 0], // increment opcode, saturating mode
+        };
+
+        let result = run_increment_counter_guarded(
+            &program_id,
+            &[INCREMENT_GUARD_MODE_REJECT_DUPLICATE, 0], // saturating mode
+            vec![this_instruction],
+            0,
+        );
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_increment_counter_guarded_reject_duplicate_rejects_sibling_increment() {
+        let program_id = Pubkey::new_unique();
+        let this_instruction = BorrowedInstruction {
+            program_id: &program_id,
+            accounts: vec![],
+            data: &[5, INCREMENT_GUARD_MODE_REJECT_DUPLICATE, 0],
+        };
+        let sibling_increment = BorrowedInstruction {
+            program_id: &program_id,
+            accounts: vec![],
+            data: &[1, 0], // another increment targeting this same program
+        };
+
+        let result = run_increment_counter_guarded(
+            &program_id,
+            &[INCREMENT_GUARD_MODE_REJECT_DUPLICATE, 0],
+            vec![this_instruction, sibling_increment],
+            0,
+        );
+        assert_eq!(
+            result,
+            Err(ProgramError::InvalidInstructionData),
+            "{:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_increment_counter_guarded_require_companion_succeeds_when_present() {
+        let program_id = Pubkey::new_unique();
+        let companion_program_id = Pubkey::new_unique();
+
+        let this_instruction = BorrowedInstruction {
+            program_id: &program_id,
+            accounts: vec![],
+            data: &[5, INCREMENT_GUARD_MODE_REQUIRE_COMPANION, 0],
+        };
+        let companion_instruction = BorrowedInstruction {
+            program_id: &companion_program_id,
+            accounts: vec![],
+            data: &[],
+        };
+
+        let mut mode_and_payload = vec![INCREMENT_GUARD_MODE_REQUIRE_COMPANION, 0];
+        mode_and_payload.extend_from_slice(companion_program_id.as_ref());
+
+        let result = run_increment_counter_guarded(
+            &program_id,
+            &mode_and_payload,
+            vec![this_instruction, companion_instruction],
+            0,
+        );
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_increment_counter_guarded_require_companion_rejects_when_missing() {
+        let program_id = Pubkey::new_unique();
+        let companion_program_id = Pubkey::new_unique();
+
+        let this_instruction = BorrowedInstruction {
+            program_id: &program_id,
+            accounts: vec![],
+            data: &[5, INCREMENT_GUARD_MODE_REQUIRE_COMPANION, 0],
+        };
+
+        let mut mode_and_payload = vec![INCREMENT_GUARD_MODE_REQUIRE_COMPANION, 0];
+        mode_and_payload.extend_from_slice(companion_program_id.as_ref());
+
+        let result = run_increment_counter_guarded(
+            &program_id,
+            &mode_and_payload,
+            vec![this_instruction], // no sibling instruction from companion_program_id
+            0,
+        );
         assert_eq!(
-            account.try_borrow_data().unwrap()[..8],
-            2u64.to_le_bytes(),
-            "Counter was not incremented correctly"
+            result,
+            Err(ProgramError::InvalidInstructionData),
+            "{:?}",
+            result
         );
     }
 }